@@ -9,11 +9,16 @@ use std::{self, mem, ops};
 use std::ffi::CStr;
 
 use ffi;
+use PyObject;
 use pointers::PyPtr;
-use python::{Python, ToPythonPointer};
-use err::PyResult;
+use python::{Python, ToPythonPointer, IntoPythonPointer};
+use err::{PyErr, PyResult};
+use conversion::ToPyTuple;
 use native::PyNativeObject;
 use super::tuple::PyTuple;
+use super::string::PyString;
+use super::dict::PyDict;
+use super::module::PyModule;
 use super::typeobject::PyType;
 
 macro_rules! exc_type(
@@ -28,6 +33,23 @@ macro_rules! exc_type(
                 unsafe { PyType::from_type_ptr(py, ffi::$exc_name as *mut ffi::PyTypeObject) }
             }
         }
+
+        impl $name {
+            /// Creates a new `PyErr` of this exception type, normalizing `args`
+            /// into the exception's `args` tuple (mirrors `raise $name(*args)`).
+            pub fn new_err<'p, A>(py: Python<'p>, args: A) -> PyErr
+                where A: ToPyTuple
+            {
+                PyErr::new::<$name, A>(py, args)
+            }
+
+            /// Consumes this marker, building a no-arg `PyErr` of this type.
+            /// The `self` parameter exists only for symmetry with `new_err`;
+            /// the marker itself carries no data.
+            pub fn into_err(self, py: Python) -> PyErr {
+                $name::new_err(py, ())
+            }
+        }
     );
 );
 
@@ -109,6 +131,119 @@ impl UnicodeDecodeError {
 }
 
 
+impl UnicodeEncodeError {
+
+    pub fn new(py: Python, encoding: &CStr, input: &str, range: ops::Range<usize>, reason: &CStr)
+               -> PyResult<PyPtr<UnicodeEncodeError>>
+    {
+        unsafe {
+            let input: Vec<ffi::Py_UNICODE> = input.chars().map(|c| c as ffi::Py_UNICODE).collect();
+            PyPtr::from_owned_ptr_or_err(
+                py, ffi::PyUnicodeEncodeError_Create(
+                    encoding.as_ptr(),
+                    input.as_ptr(),
+                    input.len() as ffi::Py_ssize_t,
+                    range.start as ffi::Py_ssize_t,
+                    range.end as ffi::Py_ssize_t,
+                    reason.as_ptr()))
+        }
+    }
+}
+
+
+impl UnicodeTranslateError {
+
+    pub fn new(py: Python, input: &str, range: ops::Range<usize>, reason: &CStr)
+               -> PyResult<PyPtr<UnicodeTranslateError>>
+    {
+        unsafe {
+            let input: Vec<ffi::Py_UNICODE> = input.chars().map(|c| c as ffi::Py_UNICODE).collect();
+            PyPtr::from_owned_ptr_or_err(
+                py, ffi::PyUnicodeTranslateError_Create(
+                    input.as_ptr(),
+                    input.len() as ffi::Py_ssize_t,
+                    range.start as ffi::Py_ssize_t,
+                    range.end as ffi::Py_ssize_t,
+                    reason.as_ptr()))
+        }
+    }
+}
+
+
+macro_rules! unicode_error_accessors(
+    ($name:ident, $get_object:path, $get_start:path, $get_end:path, $get_reason:path) => (
+        impl PyPtr<$name> {
+            /// The object that failed to decode/encode/translate.
+            pub fn object(&self, py: Python) -> PyResult<PyObject> {
+                unsafe { PyObject::from_owned_ptr_or_err(py, $get_object(self.as_ptr())) }
+            }
+
+            /// Start index of the offending range within `object()`.
+            pub fn start(&self, py: Python) -> PyResult<usize> {
+                let mut start: ffi::Py_ssize_t = 0;
+                unsafe {
+                    if $get_start(self.as_ptr(), &mut start) < 0 {
+                        return Err(PyErr::fetch(py));
+                    }
+                }
+                Ok(start as usize)
+            }
+
+            /// End index (exclusive) of the offending range within `object()`.
+            pub fn end(&self, py: Python) -> PyResult<usize> {
+                let mut end: ffi::Py_ssize_t = 0;
+                unsafe {
+                    if $get_end(self.as_ptr(), &mut end) < 0 {
+                        return Err(PyErr::fetch(py));
+                    }
+                }
+                Ok(end as usize)
+            }
+
+            /// Human readable explanation of why the conversion failed.
+            pub fn reason(&self, py: Python) -> PyResult<PyPtr<PyString>> {
+                unsafe { PyPtr::from_owned_ptr_or_err(py, $get_reason(self.as_ptr())) }
+            }
+        }
+    );
+);
+
+unicode_error_accessors!(UnicodeDecodeError,
+                          ffi::PyUnicodeDecodeError_GetObject,
+                          ffi::PyUnicodeDecodeError_GetStart,
+                          ffi::PyUnicodeDecodeError_GetEnd,
+                          ffi::PyUnicodeDecodeError_GetReason);
+unicode_error_accessors!(UnicodeEncodeError,
+                          ffi::PyUnicodeEncodeError_GetObject,
+                          ffi::PyUnicodeEncodeError_GetStart,
+                          ffi::PyUnicodeEncodeError_GetEnd,
+                          ffi::PyUnicodeEncodeError_GetReason);
+unicode_error_accessors!(UnicodeTranslateError,
+                          ffi::PyUnicodeTranslateError_GetObject,
+                          ffi::PyUnicodeTranslateError_GetStart,
+                          ffi::PyUnicodeTranslateError_GetEnd,
+                          ffi::PyUnicodeTranslateError_GetReason);
+
+impl PyPtr<UnicodeDecodeError> {
+    /// The name of the codec that was in use; `UnicodeTranslateError` has no
+    /// equivalent since `str.translate` never involves a codec.
+    pub fn encoding(&self, py: Python) -> PyResult<PyPtr<PyString>> {
+        unsafe {
+            PyPtr::from_owned_ptr_or_err(py, ffi::PyUnicodeDecodeError_GetEncoding(self.as_ptr()))
+        }
+    }
+}
+
+impl PyPtr<UnicodeEncodeError> {
+    /// The name of the codec that was in use.
+    pub fn encoding(&self, py: Python) -> PyResult<PyPtr<PyString>> {
+        unsafe {
+            PyPtr::from_owned_ptr_or_err(py, ffi::PyUnicodeEncodeError_GetEncoding(self.as_ptr()))
+        }
+    }
+}
+
+
 impl StopIteration {
 
     pub fn stop_iteration<'p>(args: PyTuple<'p>) {
@@ -118,3 +253,115 @@ impl StopIteration {
         }
     }
 }
+
+
+impl PyErr {
+
+    /// Converts a `std::io::Error` into the most specific `OSError` subclass
+    /// CPython would raise for the same condition (`FileNotFoundError`,
+    /// `PermissionError`, `BrokenPipeError`, ...), preserving `errno`.
+    ///
+    /// When the error carries a raw OS error code, `OSError(errno, strerror)`
+    /// is used so that CPython's own dispatch picks the concrete subclass and
+    /// sets `.errno` for us. Otherwise we fall back to mapping `ErrorKind` to
+    /// the matching builtin and raise it directly with just the message.
+    pub fn from_io_error(py: Python, err: std::io::Error) -> PyErr {
+        let message = err.to_string();
+
+        match err.raw_os_error() {
+            Some(errno) => OSError::new_err(py, (errno, message)),
+            None => match err.kind() {
+                std::io::ErrorKind::NotFound => FileNotFoundError::new_err(py, (message,)),
+                std::io::ErrorKind::PermissionDenied => PermissionError::new_err(py, (message,)),
+                std::io::ErrorKind::ConnectionRefused =>
+                    ConnectionRefusedError::new_err(py, (message,)),
+                std::io::ErrorKind::ConnectionReset =>
+                    ConnectionResetError::new_err(py, (message,)),
+                std::io::ErrorKind::ConnectionAborted =>
+                    ConnectionAbortedError::new_err(py, (message,)),
+                std::io::ErrorKind::BrokenPipe => BrokenPipeError::new_err(py, (message,)),
+                std::io::ErrorKind::AlreadyExists => FileExistsError::new_err(py, (message,)),
+                std::io::ErrorKind::TimedOut => TimeoutError::new_err(py, (message,)),
+                std::io::ErrorKind::Interrupted => InterruptedError::new_err(py, (message,)),
+                _ => OSError::new_err(py, (message,)),
+            }
+        }
+    }
+
+    /// Creates a brand-new exception class at runtime, e.g. for an extension
+    /// module to register its own error hierarchy rather than being limited
+    /// to the built-in types above. `name` must be `module.ClassName`; when
+    /// `base` is `None` the new type derives from `Exception`, matching
+    /// `PyErr_NewException`'s own default. `dict` seeds the new class's
+    /// namespace (extra attributes/methods) the way a class body would.
+    pub fn new_exception(py: Python, name: &CStr, base: Option<&PyType>, dict: Option<&PyDict>)
+                          -> PyResult<PyPtr<PyType>>
+    {
+        unsafe {
+            let base_ptr = base.map_or(std::ptr::null_mut(), |b| b.as_ptr());
+            let dict_ptr = dict.map_or(std::ptr::null_mut(), |d| d.as_ptr());
+            PyPtr::from_owned_ptr_or_err(
+                py, ffi::PyErr_NewExceptionWithDoc(
+                    name.as_ptr() as *mut c_char, std::ptr::null(), base_ptr, dict_ptr))
+        }
+    }
+
+    /// Inserts a type created by `new_exception` into `module`'s namespace
+    /// under `name`, so `import mymod; mymod.MyError` resolves to it.
+    pub fn add_exception_to_module(module: &PyModule, name: &CStr, exc: PyPtr<PyType>) -> PyResult<()> {
+        unsafe {
+            let py = module.py();
+            // PyModule_AddObject only steals our extra reference on success;
+            // on failure it leaves it alone, so we must release it ourselves.
+            ffi::Py_INCREF(exc.as_ptr());
+            if ffi::PyModule_AddObject(module.as_ptr(), name.as_ptr(), exc.as_ptr()) < 0 {
+                ffi::Py_DECREF(exc.as_ptr());
+                Err(PyErr::fetch(py))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PyPtr<PyType> {
+    /// Creates a `PyErr` of this type with the given constructor arguments.
+    /// The runtime-created-class analogue of the `new_err` that `exc_type!`
+    /// generates for the built-in exception markers.
+    pub fn new_err<A: ToPyTuple>(self, py: Python, args: A) -> PyErr {
+        PyErr::new_lazy_init(self, Some(args.to_py_tuple(py).into_object()))
+    }
+}
+
+
+impl PyErr {
+
+    /// Returns `new_exc` with its `__cause__` set to `cause`, the Rust
+    /// equivalent of the explicit `raise new_exc from cause`.
+    /// `PyException_SetCause` also sets `__suppress_context__`, so the
+    /// traceback shows the explicit cause instead of "during handling of
+    /// the above exception".
+    ///
+    /// Without this, translating e.g. an `IOError` into a `ValueError`
+    /// silently discards the original traceback.
+    pub fn from_cause(py: Python, mut new_exc: PyErr, mut cause: PyErr) -> PyErr {
+        let value = new_exc.instance(py);
+        let cause_value = cause.instance(py);
+        unsafe {
+            ffi::PyException_SetCause(value.as_ptr(), cause_value.into_ptr());
+        }
+        new_exc
+    }
+
+    /// Returns `new_exc` with its `__context__` set to `context`, mirroring
+    /// what CPython does implicitly when one exception is raised while
+    /// another is already being handled.
+    pub fn from_context(py: Python, mut new_exc: PyErr, mut context: PyErr) -> PyErr {
+        let value = new_exc.instance(py);
+        let context_value = context.instance(py);
+        unsafe {
+            ffi::PyException_SetContext(value.as_ptr(), context_value.into_ptr());
+        }
+        new_exc
+    }
+}
@@ -157,6 +157,32 @@ impl<'p> pyptr<'p> {
             pyptr::cast_from_owned_ptr::<T>(py, ptr).map_err(|e| e.into())
         }
     }
+
+    /// Casts this untyped handle into a concrete, typed `PyPtr<T>`,
+    /// consuming it. Unlike `cast_from_owned_ptr`/`cast_from_borrowed_ptr`
+    /// (which take a raw FFI pointer and hand back another untyped
+    /// `pyptr`), this keeps the target type at the API level so callers
+    /// don't immediately lose it again.
+    pub fn cast_into<T>(self) -> Result<::pointers::PyPtr<T>, ::PyDowncastError<'p>>
+        where T: PyTypeInfo
+    {
+        let checked = unsafe { ffi::PyObject_TypeCheck(self.1, T::type_object()) != 0 };
+
+        if checked {
+            let py = self.0;
+            let ptr = self.into_ptr();
+            Ok(unsafe { ::pointers::PyPtr::from_owned_ptr(py, ptr) })
+        } else {
+            Err(::PyDowncastError(self.0, None))
+        }
+    }
+
+    /// Creates a new owning handle to the same object, incrementing its
+    /// reference count. Safe counterpart to going through the raw pointer
+    /// and calling `Py_INCREF` by hand.
+    pub fn clone_ref(&self) -> pyptr<'p> {
+        unsafe { pyptr::from_borrowed_ptr(self.0, self.1) }
+    }
 }
 
 impl<'p> ToPythonPointer for pyptr<'p> {
@@ -182,8 +208,9 @@ impl<'p> IntoPythonPointer for pyptr<'p> {
 impl<'p> Drop for pyptr<'p> {
 
     fn drop(&mut self) {
+        #[cfg(feature = "trace-refcount")]
         unsafe {
-            println!("drop pyptr: {:?} {} {:?}",
+            println!("drop pyptr: {:?} refcnt={} at {:?}",
                      self.1, ffi::Py_REFCNT(self.1), &self as *const _);
         }
         unsafe { ffi::Py_DECREF(self.1); }